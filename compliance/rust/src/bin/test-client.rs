@@ -1,11 +1,12 @@
 use clap::{Arg, Command};
-use mcp_compliance_rust::{load_scenarios, ScenarioDefinition};
+use mcp_compliance_rust::{load_scenarios, ExpectClause, ScenarioDefinition, ScenarioStep};
+use mcp_compliance_rust::framing::FramingMode;
 use mcp_compliance_rust::jsonrpc::*;
+use mcp_compliance_rust::transport::{HttpTransport, StdioTransport, Transport};
 use serde_json::{json, Value};
-use std::io::{BufRead, BufReader, Write};
 use std::process::{Command as ProcessCommand, Stdio};
 use tokio;
-use tracing::{info, error};
+use tracing::info;
 use tracing_subscriber;
 
 #[tokio::main]
@@ -31,7 +32,25 @@ async fn main() -> anyhow::Result<()> {
         .subcommand(
             Command::new("stdio")
                 .about("Connect via stdio transport")
-                .arg(Arg::new("command").help("Server command").num_args(1..)),
+                .arg(Arg::new("command").help("Server command").num_args(1..))
+                .arg(
+                    Arg::new("framing")
+                        .long("framing")
+                        .value_name("MODE")
+                        .help("Message framing: ndjson (default) or content-length")
+                        .default_value("ndjson"),
+                ),
+        )
+        .subcommand(
+            Command::new("http")
+                .about("Connect via Streamable HTTP transport")
+                .arg(
+                    Arg::new("endpoint")
+                        .long("endpoint")
+                        .value_name("URL")
+                        .help("Server's Streamable HTTP endpoint")
+                        .required(true),
+                ),
         )
         .get_matches();
 
@@ -51,11 +70,15 @@ async fn main() -> anyhow::Result<()> {
 
     match matches.subcommand() {
         Some(("stdio", sub_matches)) => {
+            if scenario.http_only {
+                return Err(anyhow::anyhow!("Scenario {} is http_only and cannot run over stdio", scenario_id));
+            }
+
             let args: Vec<String> = sub_matches.get_many::<String>("command")
                 .unwrap_or_default()
                 .cloned()
                 .collect();
-            
+
             if args.is_empty() {
                 return Err(anyhow::anyhow!("No server command provided"));
             }
@@ -67,43 +90,24 @@ async fn main() -> anyhow::Result<()> {
                 .stderr(Stdio::piped())
                 .spawn()?;
 
-            let mut stdin = child.stdin.take().unwrap();
-            let stdout = child.stdout.take().unwrap();
-            let mut reader = BufReader::new(stdout);
+            let framing = FramingMode::from_arg(sub_matches.get_one::<String>("framing").unwrap())?;
 
-            let init_request = JsonRpcRequest {
-                jsonrpc: "2.0".to_string(),
-                id: Some(json!(1)),
-                method: "initialize".to_string(),
-                params: Some(json!({
-                    "protocolVersion": "2025-06-18",
-                    "capabilities": {},
-                    "clientInfo": {
-                        "name": "RustTestClient",
-                        "version": "1.0.0"
-                    }
-                })),
-            };
-
-            writeln!(stdin, "{}", serde_json::to_string(&init_request)?)?;
-
-            let mut response_line = String::new();
-            loop {
-                response_line.clear();
-                reader.read_line(&mut response_line)?;
-                let line = response_line.trim();
-                if line.starts_with('{') {
-                    info!("Initialize response: {}", line);
-                    break;
-                }
-            }
+            let stdin = child.stdin.take().unwrap();
+            let stdout = child.stdout.take().unwrap();
+            let mut transport = StdioTransport::with_framing(stdin, stdout, framing);
 
-            execute_scenario(&mut stdin, &mut reader, scenario, client_id).await?;
+            run_scenario(&mut transport, scenario, client_id).await?;
 
             child.wait()?;
         }
+        Some(("http", sub_matches)) => {
+            let endpoint = sub_matches.get_one::<String>("endpoint").unwrap();
+            let mut transport = HttpTransport::new(endpoint.clone());
+
+            run_scenario(&mut transport, scenario, client_id).await?;
+        }
         _ => {
-            return Err(anyhow::anyhow!("Only stdio transport supported currently"));
+            return Err(anyhow::anyhow!("No transport specified"));
         }
     };
 
@@ -111,64 +115,552 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Performs the `initialize` handshake over `transport`, then hands off to
+/// `execute_scenario`. Shared by every transport so the handshake can't drift
+/// between them.
+async fn run_scenario(
+    transport: &mut impl Transport,
+    scenario: &ScenarioDefinition,
+    client_id: &str,
+) -> anyhow::Result<()> {
+    let mut next_id = 1u64;
+    let init_id = next_id;
+    next_id += 1;
+
+    let init_request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(json!(init_id)),
+        method: "initialize".to_string(),
+        params: Some(json!({
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": {
+                "name": "RustTestClient",
+                "version": "1.0.0"
+            }
+        })),
+    };
+
+    transport.send(&init_request)?;
+    let init_response = transport.recv(&json!(init_id))?;
+    info!("Initialize response: {:?}", init_response);
+
+    transport.send_notification(&JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: "notifications/initialized".to_string(),
+        params: None,
+    })?;
+
+    execute_scenario(transport, scenario, client_id, &mut next_id).await?;
+
+    shutdown(transport, &mut next_id)?;
+    Ok(())
+}
+
+/// Runs the LSP-style shutdown sequence: a `shutdown` request the server
+/// must acknowledge, then an `exit` notification telling it to terminate.
+fn shutdown(transport: &mut impl Transport, next_id: &mut u64) -> anyhow::Result<()> {
+    let id = *next_id;
+    *next_id += 1;
+
+    transport.send(&JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(json!(id)),
+        method: "shutdown".to_string(),
+        params: None,
+    })?;
+    transport.recv(&json!(id))?;
+
+    transport.send_notification(&JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: "exit".to_string(),
+        params: None,
+    })
+}
+
+/// Runs every `ScenarioStep` in `scenario.steps` in order, sending each as a
+/// `JsonRpcRequest` with an id from `next_id` and checking its `expect`
+/// clause (if any) against the matching response. Generic over `Transport`
+/// so the same steps run unchanged over stdio or Streamable HTTP. A run of
+/// consecutive steps marked `batch` is sent together as one JSON-RPC batch
+/// via [`run_batch`] instead of one request per round trip.
+///
+/// Each step's response is captured in order, so a later step's `params` can
+/// reference an earlier one via `"$steps[N].<path>"` (e.g.
+/// `"$steps[0].result.content[0].text"`), letting a scenario feed one tool's
+/// output into the next call.
 async fn execute_scenario(
-    stdin: &mut std::process::ChildStdin,
-    reader: &mut BufReader<std::process::ChildStdout>,
+    transport: &mut impl Transport,
     scenario: &ScenarioDefinition,
     _client_id: &str,
+    next_id: &mut u64,
 ) -> anyhow::Result<()> {
-    match scenario.id {
-        1 => {
-            let tool_request = JsonRpcRequest {
-                jsonrpc: "2.0".to_string(),
-                id: Some(json!(2)),
-                method: "tools/call".to_string(),
-                params: Some(json!({
-                    "name": "add",
-                    "arguments": {"a": 10, "b": 20}
-                })),
-            };
+    if scenario.steps.is_empty() {
+        info!("Scenario {} has no steps defined", scenario.id);
+        return Ok(());
+    }
 
-            writeln!(stdin, "{}", serde_json::to_string(&tool_request)?)?;
-
-            let mut response_line = String::new();
-            loop {
-                response_line.clear();
-                reader.read_line(&mut response_line)?;
-                let line = response_line.trim();
-                if line.starts_with('{') {
-                    info!("Raw tool call response: {}", line);
-                    break;
-                }
-            }
-            let response: JsonRpcResponse = serde_json::from_str(&response_line.trim())?;
-            
-            if let Some(result) = response.result {
-                if let Some(content_array) = result.get("content") {
-                    if let Some(first_content) = content_array.get(0) {
-                        if let Some(text) = first_content.get("text") {
-                            if text == "30" {
-                                info!("Scenario 1 passed: got expected result 30");
-                            } else {
-                                return Err(anyhow::anyhow!("Scenario 1 failed: expected 30, got {}", text));
-                            }
-                        } else {
-                            return Err(anyhow::anyhow!("No text field in content"));
-                        }
-                    } else {
-                        return Err(anyhow::anyhow!("No content in response"));
-                    }
+    let mut captured: Vec<Value> = Vec::new();
+    let mut index = 0;
+
+    while index < scenario.steps.len() {
+        if scenario.steps[index].batch {
+            let batch_len = scenario.steps[index..].iter().take_while(|s| s.batch).count();
+            run_batch(transport, scenario, &scenario.steps[index..index + batch_len], index, &mut captured, next_id)?;
+            index += batch_len;
+        } else {
+            run_step(transport, scenario, &scenario.steps[index], index, &mut captured, next_id)?;
+            index += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends one step, waits for its response, captures it, then checks `expect`.
+fn run_step(
+    transport: &mut impl Transport,
+    scenario: &ScenarioDefinition,
+    step: &ScenarioStep,
+    index: usize,
+    captured: &mut Vec<Value>,
+    next_id: &mut u64,
+) -> anyhow::Result<()> {
+    let id = *next_id;
+    *next_id += 1;
+
+    let params = step
+        .params
+        .as_ref()
+        .map(|p| resolve_step_references(p, captured))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Scenario {} step {} ({}): {}", scenario.id, index, step.method, e))?;
+
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(json!(id)),
+        method: step.method.clone(),
+        params,
+    };
+
+    info!("Step {}: sending {} (id {})", index, step.method, id);
+    transport.send(&request)?;
+
+    let response = transport.recv(&json!(id))?;
+    captured.push(serde_json::to_value(&response)?);
+
+    if let Some(expect) = &step.expect {
+        check_expect(step, expect, &response)
+            .map_err(|e| anyhow::anyhow!("Scenario {} step {} ({}) failed: {}", scenario.id, index, step.method, e))?;
+    }
+
+    Ok(())
+}
+
+/// Sends a run of consecutive `batch`-marked steps as a single JSON-RPC batch
+/// request (via [`Transport::send_batch`]), then captures and checks each
+/// step's response individually, same as [`run_step`] would.
+fn run_batch(
+    transport: &mut impl Transport,
+    scenario: &ScenarioDefinition,
+    steps: &[ScenarioStep],
+    start_index: usize,
+    captured: &mut Vec<Value>,
+    next_id: &mut u64,
+) -> anyhow::Result<()> {
+    let mut requests = Vec::with_capacity(steps.len());
+    for step in steps {
+        let id = *next_id;
+        *next_id += 1;
+
+        let params = step
+            .params
+            .as_ref()
+            .map(|p| resolve_step_references(p, captured))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Scenario {} batch step {} ({}): {}", scenario.id, start_index, step.method, e))?;
+
+        requests.push(JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(id)),
+            method: step.method.clone(),
+            params,
+        });
+    }
+
+    info!("Batch: sending {} steps starting at step {}", steps.len(), start_index);
+    let responses = transport.send_batch(&requests)?;
+
+    for (offset, (step, request)) in steps.iter().zip(&requests).enumerate() {
+        let index = start_index + offset;
+        let response = responses
+            .iter()
+            .find(|r| r.id == request.id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Scenario {} step {} ({}): no response in batch reply", scenario.id, index, step.method))?;
+
+        captured.push(serde_json::to_value(&response)?);
+
+        if let Some(expect) = &step.expect {
+            check_expect(step, expect, &response)
+                .map_err(|e| anyhow::anyhow!("Scenario {} step {} ({}) failed: {}", scenario.id, index, step.method, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recursively substitutes `"$steps[N].<path>"` string values with the value
+/// at `<path>` (resolved via [`resolve_path`]) into the response captured for
+/// step `N`. A string not matching that form is left untouched.
+///
+/// Tool text content (e.g. `content[0].text`) is always a JSON string even
+/// when it represents a number, so a resolved string that parses as a JSON
+/// number is coerced to that number — otherwise chaining a result straight
+/// into a numeric argument (e.g. `add` into `add`) would silently compute
+/// with the string's `as_f64` fallback of `0` instead of erroring or working.
+fn resolve_step_references(value: &Value, captured: &[Value]) -> anyhow::Result<Value> {
+    match value {
+        Value::String(s) => match s.strip_prefix("$steps[") {
+            Some(rest) => {
+                let close = rest.find(']')
+                    .ok_or_else(|| anyhow::anyhow!("malformed step reference '{}': missing ']'", s))?;
+                let step_index: usize = rest[..close].parse()
+                    .map_err(|_| anyhow::anyhow!("malformed step reference '{}': '{}' is not a step index", s, &rest[..close]))?;
+                let path = rest[close + 1..].strip_prefix('.').unwrap_or("");
+
+                let step_result = captured.get(step_index)
+                    .ok_or_else(|| anyhow::anyhow!("step reference '{}' refers to step {} but only {} step(s) have run", s, step_index, captured.len()))?;
+
+                let resolved = if path.is_empty() {
+                    step_result.clone()
                 } else {
-                    return Err(anyhow::anyhow!("No content field in result"));
-                }
-            } else {
-                return Err(anyhow::anyhow!("No result in response: {:?}", response));
+                    resolve_path(step_result, path)
+                        .ok_or_else(|| anyhow::anyhow!("step reference '{}' did not resolve against {}", s, step_result))?
+                        .clone()
+                };
+                Ok(coerce_numeric_string(resolved))
+            }
+            None => Ok(value.clone()),
+        },
+        Value::Array(items) => Ok(Value::Array(
+            items.iter().map(|v| resolve_step_references(v, captured)).collect::<anyhow::Result<Vec<_>>>()?,
+        )),
+        Value::Object(map) => {
+            let mut resolved = serde_json::Map::with_capacity(map.len());
+            for (key, v) in map {
+                resolved.insert(key.clone(), resolve_step_references(v, captured)?);
             }
+            Ok(Value::Object(resolved))
         }
-        _ => {
-            info!("Scenario {} not fully implemented yet", scenario.id);
+        other => Ok(other.clone()),
+    }
+}
+
+/// Evaluates a step's `ExpectClause` against its response, producing an error
+/// with both the expected and actual value on mismatch.
+fn check_expect(step: &ScenarioStep, expect: &ExpectClause, response: &JsonRpcResponse) -> anyhow::Result<()> {
+    if let Some(want_error) = expect.is_error {
+        let got_error = response.error.is_some()
+            || response
+                .result
+                .as_ref()
+                .and_then(|r| r.get("isError"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+        if got_error != want_error {
+            return Err(anyhow::anyhow!("expected is_error={}, got {}", want_error, got_error));
+        }
+    }
+
+    if let Some(want_code) = expect.error_code {
+        let got_code = response.error.as_ref().map(|e| e.code);
+        if got_code != Some(want_code) {
+            return Err(anyhow::anyhow!("expected error_code={}, got {:?}", want_code, got_code));
+        }
+    }
+
+    if expect.equals.is_some() || expect.contains.is_some() {
+        let result = response
+            .result
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("expected a result to check against {:?}, got error {:?}", step.method, response.error))?;
+        let actual = match &expect.result_path {
+            Some(path) => resolve_path(result, path)
+                .ok_or_else(|| anyhow::anyhow!("result path '{}' did not resolve against {}", path, result))?,
+            None => result,
+        };
+
+        if let Some(expected) = &expect.equals {
+            if actual != expected {
+                return Err(anyhow::anyhow!("expected {} == {}, got {}", expect.result_path.as_deref().unwrap_or("result"), expected, actual));
+            }
+        }
+
+        if let Some(expected) = &expect.contains {
+            let matches = match actual {
+                Value::String(s) => expected.as_str().map(|sub| s.contains(sub)).unwrap_or(false),
+                Value::Array(items) => items.contains(expected),
+                _ => false,
+            };
+            if !matches {
+                return Err(anyhow::anyhow!("expected {} to contain {}, got {}", expect.result_path.as_deref().unwrap_or("result"), expected, actual));
+            }
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// If `value` is a string holding a valid JSON number (e.g. tool text content
+/// like `"30"`), returns that number instead; otherwise returns `value`
+/// unchanged.
+fn coerce_numeric_string(value: Value) -> Value {
+    match &value {
+        Value::String(s) => match serde_json::from_str::<Value>(s) {
+            Ok(number @ Value::Number(_)) => number,
+            _ => value,
+        },
+        _ => value,
+    }
+}
+
+/// Resolves a dotted/indexed path like `content[0].text` against `value`,
+/// returning `None` if any segment is missing.
+fn resolve_path<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    let mut current = value;
+    for raw_segment in path.split('.') {
+        let (key, indices) = split_indices(raw_segment);
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices {
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+/// Splits `foo[0][1]` into (`"foo"`, `[0, 1]`); `[0]` alone yields (`""`, `[0]`).
+fn split_indices(segment: &str) -> (&str, Vec<usize>) {
+    let bracket_start = segment.find('[');
+    let key = match bracket_start {
+        Some(pos) => &segment[..pos],
+        None => segment,
+    };
+
+    let mut indices = Vec::new();
+    let mut rest = match bracket_start {
+        Some(pos) => &segment[pos..],
+        None => "",
+    };
+    while let Some(close) = rest.find(']') {
+        if let Ok(index) = rest[1..close].parse::<usize>() {
+            indices.push(index);
+        }
+        rest = &rest[close + 1..];
+    }
+
+    (key, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_indices_plain_key() {
+        assert_eq!(split_indices("content"), ("content", vec![]));
+    }
+
+    #[test]
+    fn split_indices_single_index() {
+        assert_eq!(split_indices("content[0]"), ("content", vec![0]));
+    }
+
+    #[test]
+    fn split_indices_chained_indices() {
+        assert_eq!(split_indices("content[0][1]"), ("content", vec![0, 1]));
+    }
+
+    #[test]
+    fn split_indices_index_only() {
+        assert_eq!(split_indices("[0]"), ("", vec![0]));
+    }
+
+    #[test]
+    fn resolve_path_dotted_and_indexed() {
+        let value = json!({"content": [{"text": "hi"}]});
+        assert_eq!(resolve_path(&value, "content[0].text"), Some(&json!("hi")));
+    }
+
+    #[test]
+    fn resolve_path_nested_keys() {
+        let value = json!({"a": {"b": 2}});
+        assert_eq!(resolve_path(&value, "a.b"), Some(&json!(2)));
+    }
+
+    #[test]
+    fn resolve_path_missing_key_is_none() {
+        let value = json!({"a": 1});
+        assert_eq!(resolve_path(&value, "missing"), None);
+    }
+
+    #[test]
+    fn resolve_path_out_of_range_index_is_none() {
+        let value = json!({"content": [{"text": "hi"}]});
+        assert_eq!(resolve_path(&value, "content[5].text"), None);
+    }
+
+    #[test]
+    fn resolve_step_references_substitutes_captured_path() {
+        let captured = vec![json!({"result": {"content": [{"text": "30"}]}})];
+        let value = json!("$steps[0].result.content[0].text");
+        assert_eq!(resolve_step_references(&value, &captured).unwrap(), json!(30));
+    }
+
+    #[test]
+    fn resolve_step_references_non_numeric_string_is_untouched() {
+        let captured = vec![json!({"result": {"content": [{"text": "hello"}]}})];
+        let value = json!("$steps[0].result.content[0].text");
+        assert_eq!(resolve_step_references(&value, &captured).unwrap(), json!("hello"));
+    }
+
+    #[test]
+    fn resolve_step_references_whole_step_without_path() {
+        let captured = vec![json!({"result": {"ok": true}})];
+        let value = json!("$steps[0]");
+        assert_eq!(resolve_step_references(&value, &captured).unwrap(), captured[0]);
+    }
+
+    #[test]
+    fn resolve_step_references_plain_string_is_untouched() {
+        let captured: Vec<Value> = vec![];
+        let value = json!("just a string");
+        assert_eq!(resolve_step_references(&value, &captured).unwrap(), value);
+    }
+
+    #[test]
+    fn resolve_step_references_recurses_into_objects_and_arrays() {
+        let captured = vec![json!({"result": {"content": [{"text": "30"}]}})];
+        let value = json!({
+            "name": "add",
+            "arguments": {"a": "$steps[0].result.content[0].text", "b": 5},
+            "tags": ["$steps[0].result.content[0].text", "literal"]
+        });
+        let resolved = resolve_step_references(&value, &captured).unwrap();
+        assert_eq!(resolved["arguments"]["a"], json!(30));
+        assert_eq!(resolved["arguments"]["b"], json!(5));
+        assert_eq!(resolved["tags"][0], json!(30));
+        assert_eq!(resolved["tags"][1], json!("literal"));
+    }
+
+    #[test]
+    fn coerce_numeric_string_parses_plain_number() {
+        assert_eq!(coerce_numeric_string(json!("30")), json!(30));
+    }
+
+    #[test]
+    fn coerce_numeric_string_leaves_non_numeric_alone() {
+        assert_eq!(coerce_numeric_string(json!("hello")), json!("hello"));
+    }
+
+    #[test]
+    fn coerce_numeric_string_leaves_non_string_alone() {
+        assert_eq!(coerce_numeric_string(json!(5)), json!(5));
+        assert_eq!(coerce_numeric_string(json!(true)), json!(true));
+    }
+
+    #[test]
+    fn resolve_step_references_out_of_range_step_is_error() {
+        let captured = vec![json!({"result": {}})];
+        let value = json!("$steps[1].result");
+        assert!(resolve_step_references(&value, &captured).is_err());
+    }
+
+    #[test]
+    fn resolve_step_references_unresolvable_path_is_error() {
+        let captured = vec![json!({"result": {}})];
+        let value = json!("$steps[0].result.missing");
+        assert!(resolve_step_references(&value, &captured).is_err());
+    }
+
+    /// A minimal stand-in for `CalcServer`'s `add` tool, mirroring
+    /// `test-server.rs`'s handler exactly (including its `as_f64` extraction),
+    /// so the test below drives a real add→add round trip through
+    /// `execute_scenario` instead of only unit-testing the substitution
+    /// mechanism in isolation.
+    struct FakeAddServer {
+        pending: std::collections::HashMap<Value, JsonRpcResponse>,
+    }
+
+    impl Transport for FakeAddServer {
+        fn send(&mut self, request: &JsonRpcRequest) -> anyhow::Result<()> {
+            let args = request
+                .params
+                .as_ref()
+                .and_then(|p| p.get("arguments"))
+                .cloned()
+                .unwrap_or(json!({}));
+            let a = args["a"].as_f64().unwrap_or(0.0);
+            let b = args["b"].as_f64().unwrap_or(0.0);
+
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id.clone(),
+                result: Some(json!({"content": [{"type": "text", "text": (a + b).to_string()}], "isError": false})),
+                error: None,
+            };
+            if let Some(id) = request.id.clone() {
+                self.pending.insert(id, response);
+            }
+            Ok(())
+        }
+
+        fn recv(&mut self, expected_id: &Value) -> anyhow::Result<JsonRpcResponse> {
+            self.pending
+                .remove(expected_id)
+                .ok_or_else(|| anyhow::anyhow!("no response queued for id {}", expected_id))
+        }
+
+        fn send_notification(&mut self, _notification: &JsonRpcNotification) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn add_step(a: Value, b: Value, expect_total: &str) -> ScenarioStep {
+        ScenarioStep {
+            method: "tools/call".to_string(),
+            params: Some(json!({"name": "add", "arguments": {"a": a, "b": b}})),
+            expect: Some(ExpectClause {
+                result_path: Some("content[0].text".to_string()),
+                equals: Some(json!(expect_total)),
+                contains: None,
+                is_error: None,
+                error_code: None,
+            }),
+            batch: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_scenario_chains_numeric_add_result_into_second_add() {
+        let scenario = ScenarioDefinition {
+            id: 1,
+            description: "add(10, 20) then add the result to 5".to_string(),
+            client_ids: vec!["client1".to_string()],
+            server_name: "CalcServer".to_string(),
+            http_only: false,
+            steps: vec![
+                add_step(json!(10), json!(20), "30"),
+                add_step(json!("$steps[0].result.content[0].text"), json!(5), "35"),
+            ],
+        };
+
+        let mut transport = FakeAddServer { pending: std::collections::HashMap::new() };
+        let mut next_id = 1;
+        execute_scenario(&mut transport, &scenario, "client1", &mut next_id)
+            .await
+            .expect("chained add scenario should resolve the captured result as a number, not let as_f64 read the string as 0");
+    }
+}
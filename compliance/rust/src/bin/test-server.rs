@@ -1,8 +1,17 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
 use clap::{Arg, Command};
-use mcp_compliance_rust::load_scenarios;
-use mcp_compliance_rust::jsonrpc::*;
+use mcp_compliance_rust::{load_scenarios, ParamDefinition, ServerDefinition};
+use mcp_compliance_rust::framing::{self, FramingMode};
+use mcp_compliance_rust::jsonrpc::{CallToolParams, Incoming, JsonRpcError, JsonRpcRequest, JsonRpcResponse};
 use serde_json::{json, Value};
-use std::io::{self, BufRead, BufReader, Write};
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio;
 use tracing::{info, warn};
 use tracing_subscriber;
@@ -24,71 +33,219 @@ async fn main() -> anyhow::Result<()> {
             Arg::new("transport")
                 .long("transport")
                 .value_name("TRANSPORT")
-                .help("Transport type (stdio)")
+                .help("Transport type: stdio or http")
                 .required(true),
         )
+        .arg(
+            Arg::new("framing")
+                .long("framing")
+                .value_name("MODE")
+                .help("Message framing for stdio: ndjson (default) or content-length")
+                .default_value("ndjson"),
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .value_name("PORT")
+                .help("TCP port to listen on (required for http transport)"),
+        )
         .get_matches();
 
     let server_name = matches.get_one::<String>("server-name").unwrap();
+    let transport = matches.get_one::<String>("transport").unwrap();
 
     info!("Starting {} server", server_name);
 
+    let scenarios = load_scenarios()?;
+    let server_def = scenarios
+        .servers
+        .get(server_name.as_str())
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Unknown server '{}': not present in scenarios data", server_name))?;
+
+    match transport.as_str() {
+        "stdio" => {
+            let framing = FramingMode::from_arg(matches.get_one::<String>("framing").unwrap())?;
+            run_stdio_server(server_name, &server_def, framing).await
+        }
+        "http" => {
+            let port: u16 = matches
+                .get_one::<String>("port")
+                .ok_or_else(|| anyhow::anyhow!("--port is required for http transport"))?
+                .parse()?;
+            run_http_server(port, server_name.clone(), server_def).await
+        }
+        other => Err(anyhow::anyhow!("Unknown transport '{}': expected 'stdio' or 'http'", other)),
+    }
+}
+
+/// Serves JSON-RPC requests framed per `framing` over stdin/stdout, as a
+/// child process spawned by the test client.
+async fn run_stdio_server(server_name: &str, server_def: &ServerDefinition, framing: FramingMode) -> anyhow::Result<()> {
     let stdin = io::stdin();
+    let mut reader = stdin.lock();
     let mut stdout = io::stdout();
 
-    for line in stdin.lock().lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
+    loop {
+        let raw = match framing::read_message(&mut reader, framing)? {
+            Some(raw) => raw,
+            None => break,
+        };
+
+        info!("Received request: {}", raw);
 
-        info!("Received request: {}", line);
-        let request: JsonRpcRequest = match serde_json::from_str(&line) {
-            Ok(req) => req,
+        // `Incoming` is untagged, so parsing it just picks the Single or
+        // Batch branch based on whether `raw` is an object or an array.
+        let incoming: Incoming = match serde_json::from_value(raw) {
+            Ok(incoming) => incoming,
             Err(e) => {
-                let error_response = JsonRpcResponse {
-                    jsonrpc: "2.0".to_string(),
-                    id: None,
-                    result: None,
-                    error: Some(JsonRpcError {
-                        code: -32700,
-                        message: format!("Parse error: {}", e),
-                        data: None,
-                    }),
-                };
-                writeln!(stdout, "{}", serde_json::to_string(&error_response)?)?;
-                stdout.flush()?;
+                let error_response = parse_error_response(&e);
+                framing::write_message(&mut stdout, &serde_json::to_value(&error_response)?, framing)?;
                 continue;
             }
         };
 
-        let response = match handle_request(server_name, &request).await {
-            Ok(result) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                id: request.id.clone(),
-                result: Some(result),
-                error: None,
-            },
-            Err(e) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                id: request.id.clone(),
-                result: None,
-                error: Some(JsonRpcError {
-                    code: -32603,
-                    message: e.to_string(),
-                    data: None,
-                }),
-            },
-        };
-
-        writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
-        stdout.flush()?;
+        match incoming {
+            Incoming::Single(request) => {
+                if request.id.is_none() {
+                    // Notification: act on it if we need to, but never
+                    // write a response line for it, per spec.
+                    if request.method == "exit" {
+                        info!("Received exit notification, shutting down");
+                        break;
+                    }
+                    continue;
+                }
+                let response = dispatch(server_name, server_def, &request).await;
+                framing::write_message(&mut stdout, &serde_json::to_value(&response)?, framing)?;
+            }
+            Incoming::Batch(requests) => {
+                let mut responses = Vec::new();
+                for request in &requests {
+                    let response = dispatch(server_name, server_def, request).await;
+                    // Notifications carry no id and get no response, per spec.
+                    if request.id.is_some() {
+                        responses.push(response);
+                    }
+                }
+                framing::write_message(&mut stdout, &serde_json::to_value(&responses)?, framing)?;
+            }
+        }
     }
 
     Ok(())
 }
 
-async fn handle_request(server_name: &str, request: &JsonRpcRequest) -> anyhow::Result<Value> {
+/// State shared across Streamable HTTP requests: the server identity and
+/// definition needed by `dispatch`, plus a counter for minting
+/// `Mcp-Session-Id` values on `initialize`.
+struct HttpServerState {
+    server_name: String,
+    server_def: ServerDefinition,
+    next_session: AtomicU64,
+}
+
+/// Serves the MCP Streamable HTTP transport: a single POST endpoint that
+/// accepts a JSON-RPC request or batch and replies with a JSON body,
+/// dispatched through the same [`dispatch`] used by the stdio loop. Each
+/// non-batched `initialize` response is given a fresh `Mcp-Session-Id`
+/// (an `initialize` inside a batch gets none); this server is
+/// otherwise stateless, so it doesn't require the header back on later
+/// requests.
+async fn run_http_server(port: u16, server_name: String, server_def: ServerDefinition) -> anyhow::Result<()> {
+    let state = Arc::new(HttpServerState {
+        server_name,
+        server_def,
+        next_session: AtomicU64::new(1),
+    });
+
+    let app = Router::new().route("/", post(handle_http_request)).with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    info!("Listening for Streamable HTTP on http://127.0.0.1:{}", port);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn handle_http_request(State(state): State<Arc<HttpServerState>>, Json(body): Json<Value>) -> Response {
+    info!("Received request: {}", body);
+
+    let incoming: Incoming = match serde_json::from_value(body) {
+        Ok(incoming) => incoming,
+        Err(e) => return Json(parse_error_response(&e)).into_response(),
+    };
+
+    match incoming {
+        Incoming::Single(request) => {
+            if request.id.is_none() {
+                // Notification: nothing to reply with, per spec.
+                return StatusCode::ACCEPTED.into_response();
+            }
+
+            let response = dispatch(&state.server_name, &state.server_def, &request).await;
+
+            let mut headers = HeaderMap::new();
+            if request.method == "initialize" {
+                let session_id = format!("session-{}", state.next_session.fetch_add(1, Ordering::SeqCst));
+                if let Ok(value) = HeaderValue::from_str(&session_id) {
+                    headers.insert("Mcp-Session-Id", value);
+                }
+            }
+            (headers, Json(response)).into_response()
+        }
+        Incoming::Batch(requests) => {
+            let mut responses = Vec::new();
+            for request in &requests {
+                let response = dispatch(&state.server_name, &state.server_def, request).await;
+                // Notifications carry no id and get no response, per spec.
+                if request.id.is_some() {
+                    responses.push(response);
+                }
+            }
+            if responses.is_empty() {
+                StatusCode::ACCEPTED.into_response()
+            } else {
+                Json(responses).into_response()
+            }
+        }
+    }
+}
+
+fn parse_error_response(e: &serde_json::Error) -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id: None,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32700,
+            message: format!("Parse error: {}", e),
+            data: None,
+        }),
+    }
+}
+
+async fn dispatch(server_name: &str, server_def: &ServerDefinition, request: &JsonRpcRequest) -> JsonRpcResponse {
+    match handle_request(server_name, server_def, request).await {
+        Ok(result) => JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: request.id.clone(),
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: request.id.clone(),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32603,
+                message: e.to_string(),
+                data: None,
+            }),
+        },
+    }
+}
+
+async fn handle_request(server_name: &str, server_def: &ServerDefinition, request: &JsonRpcRequest) -> anyhow::Result<Value> {
     match request.method.as_str() {
         "initialize" => {
             Ok(json!({
@@ -179,8 +336,163 @@ async fn handle_request(server_name: &str, request: &JsonRpcRequest) -> anyhow::
                 }
             }
         }
+        "ping" => Ok(json!({})),
+        "shutdown" => Ok(Value::Null),
+        "resources/list" => {
+            let resources: Vec<Value> = server_def
+                .resources
+                .iter()
+                .map(|(uri, def)| {
+                    json!({
+                        "uri": uri,
+                        "name": uri,
+                        "description": def.description
+                    })
+                })
+                .collect();
+            Ok(json!({"resources": resources}))
+        }
+        "resources/templates/list" => {
+            let resource_templates: Vec<Value> = server_def
+                .resource_templates
+                .iter()
+                .map(|(uri_template, def)| {
+                    json!({
+                        "uriTemplate": uri_template,
+                        "name": uri_template,
+                        "description": def.description
+                    })
+                })
+                .collect();
+            Ok(json!({"resourceTemplates": resource_templates}))
+        }
+        "resources/read" => {
+            let params: ReadResourceParams = request.params.as_ref()
+                .map(|p| serde_json::from_value(p.clone()))
+                .transpose()?
+                .ok_or_else(|| anyhow::anyhow!("Missing resources/read parameters"))?;
+
+            if let Some(def) = server_def.resources.get(&params.uri) {
+                return Ok(json!({
+                    "contents": [{"uri": params.uri, "mimeType": "text/plain", "text": def.description}]
+                }));
+            }
+
+            for (uri_template, def) in &server_def.resource_templates {
+                let Some(captures) = match_template(uri_template, &params.uri) else {
+                    continue;
+                };
+                let arguments = Value::Object(captures.into_iter().map(|(k, v)| (k, Value::String(v))).collect());
+                let text = expand_placeholders(&def.description, &def.params, &arguments);
+                return Ok(json!({
+                    "contents": [{"uri": params.uri, "mimeType": "text/plain", "text": text}]
+                }));
+            }
+
+            Err(anyhow::anyhow!("Unknown resource: {}", params.uri))
+        }
+        "prompts/list" => {
+            let prompts: Vec<Value> = server_def
+                .prompts
+                .iter()
+                .map(|(name, def)| {
+                    json!({"name": name, "description": def.description})
+                })
+                .collect();
+            Ok(json!({"prompts": prompts}))
+        }
+        "prompts/get" => {
+            let params: GetPromptParams = request.params.as_ref()
+                .map(|p| serde_json::from_value(p.clone()))
+                .transpose()?
+                .ok_or_else(|| anyhow::anyhow!("Missing prompts/get parameters"))?;
+            let arguments = params.arguments.unwrap_or(json!({}));
+
+            let (description, params_def) = if let Some(def) = server_def.prompts.get(&params.name) {
+                (def.description.clone(), None)
+            } else if let Some(def) = server_def.prompt_templates.get(&params.name) {
+                (def.description.clone(), Some(&def.params))
+            } else {
+                return Err(anyhow::anyhow!("Unknown prompt: {}", params.name));
+            };
+
+            let text = match params_def {
+                Some(param_defs) => expand_placeholders(&description, param_defs, &arguments),
+                None => description,
+            };
+
+            Ok(json!({
+                "description": text,
+                "messages": [{
+                    "role": "user",
+                    "content": {"type": "text", "text": text}
+                }]
+            }))
+        }
         _ => {
             Err(anyhow::anyhow!("Unknown method: {}", request.method))
         }
     }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ReadResourceParams {
+    uri: String,
+}
+
+/// Matches a concrete `uri` against a `{param}`-templated pattern (one
+/// placeholder per path segment, RFC 6570 level-1 style), returning the
+/// extracted param values by name if it matches, so a real client driving
+/// `resources/read` with a plain `uri` can still be resolved against a
+/// template without any out-of-band argument channel.
+fn match_template(template: &str, uri: &str) -> Option<HashMap<String, String>> {
+    let mut captures = HashMap::new();
+    let mut template_rest = template;
+    let mut uri_rest = uri;
+
+    while let Some(start) = template_rest.find('{') {
+        let end = template_rest[start..].find('}')? + start;
+        let literal = &template_rest[..start];
+        let value_start = uri_rest.strip_prefix(literal)?;
+
+        let param_name = &template_rest[start + 1..end];
+        template_rest = &template_rest[end + 1..];
+
+        let next_literal = template_rest.split('{').next().unwrap_or(template_rest);
+        let value = if next_literal.is_empty() {
+            value_start
+        } else {
+            &value_start[..value_start.find(next_literal)?]
+        };
+        if value.is_empty() {
+            return None;
+        }
+
+        captures.insert(param_name.to_string(), value.to_string());
+        uri_rest = &value_start[value.len()..];
+    }
+
+    (uri_rest == template_rest).then_some(captures)
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GetPromptParams {
+    name: String,
+    #[serde(default)]
+    arguments: Option<Value>,
+}
+
+/// Replaces every `{param}` placeholder in `template` whose key is in
+/// `params` with the matching value from `arguments`, leaving placeholders
+/// for missing arguments untouched.
+fn expand_placeholders(template: &str, params: &HashMap<String, ParamDefinition>, arguments: &Value) -> String {
+    let mut expanded = template.to_string();
+    for key in params.keys() {
+        let Some(value) = arguments.get(key) else {
+            continue;
+        };
+        let replacement = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+        expanded = expanded.replace(&format!("{{{}}}", key), &replacement);
+    }
+    expanded
 }
\ No newline at end of file
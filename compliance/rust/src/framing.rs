@@ -0,0 +1,154 @@
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// How JSON-RPC messages are delimited on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramingMode {
+    /// One JSON value per message, reading exactly as many lines as the
+    /// value spans (so pretty-printed/multi-line JSON still parses as one
+    /// message) while skipping interleaved non-JSON lines such as logs.
+    NewlineDelimited,
+    /// A `Content-Length: N` header block followed by a blank line and
+    /// exactly `N` bytes of JSON body, as used by LSP and MCP's stdio
+    /// transport.
+    ContentLength,
+}
+
+impl FramingMode {
+    /// Parses a `--framing` CLI value. Accepts `ndjson` (the default) and
+    /// `content-length`.
+    pub fn from_arg(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "ndjson" => Ok(FramingMode::NewlineDelimited),
+            "content-length" => Ok(FramingMode::ContentLength),
+            other => Err(anyhow::anyhow!(
+                "Unknown framing mode '{}': expected 'ndjson' or 'content-length'",
+                other
+            )),
+        }
+    }
+}
+
+/// Reads one complete JSON-RPC message (request, response, notification, or
+/// batch array) from `reader` per `mode`. Returns `Ok(None)` on clean EOF.
+pub fn read_message(reader: &mut impl BufRead, mode: FramingMode) -> anyhow::Result<Option<Value>> {
+    match mode {
+        FramingMode::NewlineDelimited => read_ndjson(reader),
+        FramingMode::ContentLength => read_content_length(reader),
+    }
+}
+
+/// Writes `value` as one complete JSON-RPC message per `mode` and flushes.
+pub fn write_message(writer: &mut impl Write, value: &Value, mode: FramingMode) -> anyhow::Result<()> {
+    match mode {
+        FramingMode::NewlineDelimited => {
+            writeln!(writer, "{}", serde_json::to_string(value)?)?;
+        }
+        FramingMode::ContentLength => {
+            let body = serde_json::to_string(value)?;
+            write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads lines, tracking brace/bracket depth (honoring quoted strings and
+/// escapes) until a complete top-level JSON value has been accumulated.
+/// Lines read before any JSON has started that don't begin with `{` or `[`
+/// are discarded as non-protocol noise (e.g. a log line written to the same
+/// stream).
+fn read_ndjson(reader: &mut impl BufRead) -> anyhow::Result<Option<Value>> {
+    let mut buffer = String::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escape = false;
+    let mut started = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return if started {
+                Err(anyhow::anyhow!("EOF in the middle of a JSON message"))
+            } else {
+                Ok(None)
+            };
+        }
+
+        let chunk: &str = if started {
+            &line
+        } else {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+                continue;
+            }
+            started = true;
+            trimmed
+        };
+
+        buffer.push_str(chunk);
+        scan_depth(chunk, &mut depth, &mut in_string, &mut escape);
+
+        if depth == 0 {
+            return Ok(Some(serde_json::from_str(buffer.trim())?));
+        }
+    }
+}
+
+/// Updates brace/bracket `depth` for one chunk of JSON text, tracking string
+/// and escape state across calls so depth tracking is correct across lines.
+fn scan_depth(chunk: &str, depth: &mut i32, in_string: &mut bool, escape: &mut bool) {
+    for c in chunk.chars() {
+        if *escape {
+            *escape = false;
+            continue;
+        }
+        match c {
+            '\\' if *in_string => *escape = true,
+            '"' => *in_string = !*in_string,
+            '{' | '[' if !*in_string => *depth += 1,
+            '}' | ']' if !*in_string => *depth -= 1,
+            _ => {}
+        }
+    }
+}
+
+/// Reads a `Content-Length:` header block (ignoring any other headers) then
+/// exactly that many bytes of body.
+fn read_content_length(reader: &mut impl BufRead) -> anyhow::Result<Option<Value>> {
+    let mut content_length: Option<usize> = None;
+    let mut header_line = String::new();
+    let mut any_header_seen = false;
+
+    loop {
+        header_line.clear();
+        if reader.read_line(&mut header_line)? == 0 {
+            return if any_header_seen {
+                Err(anyhow::anyhow!("EOF while reading Content-Length headers"))
+            } else {
+                Ok(None)
+            };
+        }
+
+        let trimmed = header_line.trim_end_matches(['\r', '\n']);
+        if trimmed.is_empty() {
+            break;
+        }
+        any_header_seen = true;
+
+        if let Some(value) = trimmed.split_once(':').and_then(|(name, value)| {
+            name.eq_ignore_ascii_case("Content-Length").then_some(value)
+        }) {
+            content_length = Some(value.trim().parse()?);
+        }
+    }
+
+    let length = content_length.ok_or_else(|| anyhow::anyhow!("Missing Content-Length header"))?;
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
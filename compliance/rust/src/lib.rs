@@ -1,8 +1,9 @@
 pub mod jsonrpc;
-pub mod servers;
-pub mod client;
+pub mod transport;
+pub mod framing;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -63,6 +64,44 @@ pub struct ScenarioDefinition {
     pub server_name: String,
     #[serde(default)]
     pub http_only: bool,
+    #[serde(default)]
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// One request/expectation pair in a scenario. `params` is sent in the
+/// outgoing `JsonRpcRequest` once any `"$steps[N].<path>"` string values in it
+/// are replaced by the referenced earlier step's response value; `expect` (if
+/// present) is checked against the matching response once it arrives.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScenarioStep {
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+    #[serde(default)]
+    pub expect: Option<ExpectClause>,
+    /// When true, this step is sent together with the run of following
+    /// steps that are also marked `batch` as a single JSON-RPC batch request,
+    /// rather than as its own request/response round trip.
+    #[serde(default)]
+    pub batch: bool,
+}
+
+/// Assertion checked against a step's response. `result_path` is a
+/// JSON-pointer-ish path (`content[0].text`) resolved against `response.result`
+/// before `equals`/`contains` are applied; `is_error` and `error_code` are
+/// checked against the response/result directly and don't need a path.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExpectClause {
+    #[serde(rename = "resultPath", default)]
+    pub result_path: Option<String>,
+    #[serde(default)]
+    pub equals: Option<Value>,
+    #[serde(default)]
+    pub contains: Option<Value>,
+    #[serde(rename = "isError", default)]
+    pub is_error: Option<bool>,
+    #[serde(rename = "errorCode", default)]
+    pub error_code: Option<i64>,
 }
 
 pub fn load_scenarios() -> anyhow::Result<Scenarios> {
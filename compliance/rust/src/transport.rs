@@ -0,0 +1,232 @@
+use crate::framing::{self, FramingMode};
+use crate::jsonrpc::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::BufReader;
+
+/// Carries `JsonRpcRequest`s to a server and `JsonRpcResponse`s back,
+/// independent of whether the underlying connection is a stdio pipe or an
+/// HTTP request. `execute_scenario` is generic over this so the same steps
+/// run unchanged over either transport.
+pub trait Transport {
+    fn send(&mut self, request: &JsonRpcRequest) -> anyhow::Result<()>;
+    fn recv(&mut self, expected_id: &Value) -> anyhow::Result<JsonRpcResponse>;
+    /// Sends a notification; the receiver must not reply, so this doesn't wait.
+    fn send_notification(&mut self, notification: &JsonRpcNotification) -> anyhow::Result<()>;
+
+    /// Sends `requests` together as one JSON-RPC batch and returns their
+    /// responses correlated back to the requests they answer (by `id`, in
+    /// request order; requests without an `id` are notifications and get no
+    /// entry). The default sends each request individually for transports
+    /// with no native batch wire format; `StdioTransport` overrides this to
+    /// send one true batch array.
+    fn send_batch(&mut self, requests: &[JsonRpcRequest]) -> anyhow::Result<Vec<JsonRpcResponse>> {
+        let mut responses = Vec::new();
+        for request in requests {
+            self.send(request)?;
+            if let Some(id) = &request.id {
+                responses.push(self.recv(id)?);
+            }
+        }
+        Ok(responses)
+    }
+}
+
+/// Sends JSON-RPC over a child process's stdin/stdout, framed per `framing`.
+pub struct StdioTransport {
+    stdin: std::process::ChildStdin,
+    reader: BufReader<std::process::ChildStdout>,
+    framing: FramingMode,
+}
+
+impl StdioTransport {
+    /// Builds a transport using the existing newline-delimited framing.
+    pub fn new(stdin: std::process::ChildStdin, stdout: std::process::ChildStdout) -> Self {
+        Self::with_framing(stdin, stdout, FramingMode::NewlineDelimited)
+    }
+
+    pub fn with_framing(stdin: std::process::ChildStdin, stdout: std::process::ChildStdout, framing: FramingMode) -> Self {
+        Self {
+            stdin,
+            reader: BufReader::new(stdout),
+            framing,
+        }
+    }
+
+    /// Reads messages until one arrives that carries no `method` (a response
+    /// or a batch array) and returns it. A server-initiated `ping` is answered
+    /// inline with an empty result; every other server request or
+    /// notification is discarded.
+    fn read_non_ping_message(&mut self) -> anyhow::Result<Value> {
+        loop {
+            let raw = framing::read_message(&mut self.reader, self.framing)?
+                .ok_or_else(|| anyhow::anyhow!("Server closed its output"))?;
+
+            if let Some(method) = raw.get("method").and_then(Value::as_str) {
+                if method == "ping" {
+                    if let Some(id) = raw.get("id").cloned() {
+                        let reply = JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id: Some(id),
+                            result: Some(json!({})),
+                            error: None,
+                        };
+                        framing::write_message(&mut self.stdin, &serde_json::to_value(&reply)?, self.framing)?;
+                    }
+                }
+                continue;
+            }
+
+            return Ok(raw);
+        }
+    }
+}
+
+impl Transport for StdioTransport {
+    fn send(&mut self, request: &JsonRpcRequest) -> anyhow::Result<()> {
+        framing::write_message(&mut self.stdin, &serde_json::to_value(request)?, self.framing)
+    }
+
+    /// Reads messages until it finds the response whose `id` matches
+    /// `expected_id`, skipping notifications and any mismatched-id messages.
+    fn recv(&mut self, expected_id: &Value) -> anyhow::Result<JsonRpcResponse> {
+        loop {
+            let raw = self.read_non_ping_message()?;
+            let response: JsonRpcResponse = match serde_json::from_value(raw) {
+                Ok(response) => response,
+                Err(_) => continue,
+            };
+
+            match &response.id {
+                Some(id) if id == expected_id => return Ok(response),
+                _ => continue,
+            }
+        }
+    }
+
+    fn send_notification(&mut self, notification: &JsonRpcNotification) -> anyhow::Result<()> {
+        framing::write_message(&mut self.stdin, &serde_json::to_value(notification)?, self.framing)
+    }
+
+    /// Sends `requests` as a single JSON-RPC batch array on the wire, rather
+    /// than falling back to the trait's one-at-a-time default.
+    fn send_batch(&mut self, requests: &[JsonRpcRequest]) -> anyhow::Result<Vec<JsonRpcResponse>> {
+        framing::write_message(&mut self.stdin, &serde_json::to_value(requests)?, self.framing)?;
+
+        loop {
+            let raw = self.read_non_ping_message()?;
+            if !raw.is_array() {
+                continue;
+            }
+            let responses: Vec<JsonRpcResponse> = serde_json::from_value(raw)?;
+            return Ok(requests
+                .iter()
+                .filter_map(|req| req.id.as_ref())
+                .filter_map(|id| responses.iter().find(|r| r.id.as_ref() == Some(id)).cloned())
+                .collect());
+        }
+    }
+}
+
+/// Sends each `JsonRpcRequest` as a POST to an MCP Streamable HTTP endpoint.
+/// A plain `application/json` body and a `text/event-stream` body (where each
+/// `data:` line is a `JsonRpcResponse` frame) are both accepted. The
+/// `Mcp-Session-Id` returned from `initialize` is carried on every later
+/// request, as the spec requires.
+pub struct HttpTransport {
+    client: reqwest::blocking::Client,
+    endpoint: String,
+    session_id: Option<String>,
+    pending: HashMap<Value, JsonRpcResponse>,
+}
+
+impl HttpTransport {
+    /// `reqwest::blocking::Client::new` builds its own internal runtime and
+    /// panics if called directly from inside an existing one, so its
+    /// construction needs the same `block_in_place` treatment as `send`.
+    pub fn new(endpoint: String) -> Self {
+        let client = tokio::task::block_in_place(reqwest::blocking::Client::new);
+        Self {
+            client,
+            endpoint,
+            session_id: None,
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl Transport for HttpTransport {
+    /// `reqwest::blocking` drives its own internal runtime under the hood, so
+    /// calling it directly from inside the `#[tokio::main]` runtime panics
+    /// ("Cannot drop a runtime in a context where blocking is not allowed").
+    /// `block_in_place` hands this worker thread's other tasks off to the
+    /// rest of the (multi-threaded) runtime for the duration of the blocking
+    /// call, which is the standard way to mix the two.
+    fn send(&mut self, request: &JsonRpcRequest) -> anyhow::Result<()> {
+        tokio::task::block_in_place(|| {
+            let mut builder = self
+                .client
+                .post(&self.endpoint)
+                .header("Content-Type", "application/json")
+                .header("Accept", "application/json, text/event-stream");
+            if let Some(session_id) = &self.session_id {
+                builder = builder.header("Mcp-Session-Id", session_id);
+            }
+
+            let response = builder.json(request).send()?;
+
+            if let Some(session_id) = response.headers().get("Mcp-Session-Id") {
+                self.session_id = Some(session_id.to_str()?.to_string());
+            }
+
+            let is_event_stream = response
+                .headers()
+                .get("Content-Type")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.starts_with("text/event-stream"))
+                .unwrap_or(false);
+
+            if is_event_stream {
+                for line in response.text()?.lines() {
+                    let Some(data) = line.strip_prefix("data:") else {
+                        continue;
+                    };
+                    let frame: JsonRpcResponse = serde_json::from_str(data.trim())?;
+                    if let Some(id) = frame.id.clone() {
+                        self.pending.insert(id, frame);
+                    }
+                }
+            } else {
+                let parsed: JsonRpcResponse = response.json()?;
+                if let Some(id) = parsed.id.clone() {
+                    self.pending.insert(id, parsed);
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn recv(&mut self, expected_id: &Value) -> anyhow::Result<JsonRpcResponse> {
+        self.pending
+            .remove(expected_id)
+            .ok_or_else(|| anyhow::anyhow!("No response received for id {}", expected_id))
+    }
+
+    /// Posts the notification and discards the body; per the Streamable HTTP
+    /// spec a notification-only POST gets a bodyless `202 Accepted`.
+    fn send_notification(&mut self, notification: &JsonRpcNotification) -> anyhow::Result<()> {
+        tokio::task::block_in_place(|| {
+            let mut builder = self
+                .client
+                .post(&self.endpoint)
+                .header("Content-Type", "application/json")
+                .header("Accept", "application/json, text/event-stream");
+            if let Some(session_id) = &self.session_id {
+                builder = builder.header("Mcp-Session-Id", session_id);
+            }
+            builder.json(notification).send()?;
+            Ok(())
+        })
+    }
+}